@@ -9,9 +9,63 @@ use serde::{Deserialize, Serialize};
 #[derive(Debug, Deserialize)]
 struct YarnLockEntry {
   version: String,
+  #[serde(default)]
+  resolution: Option<String>,
+  #[serde(default)]
+  checksum: Option<String>,
+  #[serde(default)]
+  dependencies: HashMap<String, String>,
 }
 
-type PackageVersions = HashMap<String, HashSet<String>>;
+#[derive(Debug, Deserialize)]
+struct NpmLockfile {
+  #[serde(rename = "lockfileVersion")]
+  lockfile_version: u32,
+  #[serde(default)]
+  packages: HashMap<String, NpmLockPackage>,
+  #[serde(default)]
+  dependencies: HashMap<String, NpmLockDependency>,
+}
+
+#[derive(Debug, Deserialize)]
+struct NpmLockPackage {
+  version: Option<String>,
+  #[serde(default)]
+  resolved: Option<String>,
+  #[serde(default)]
+  integrity: Option<String>,
+  #[serde(default)]
+  dependencies: HashMap<String, String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct NpmLockDependency {
+  version: Option<String>,
+  #[serde(default)]
+  resolved: Option<String>,
+  #[serde(default)]
+  integrity: Option<String>,
+  #[serde(default)]
+  requires: HashMap<String, String>,
+  #[serde(default)]
+  dependencies: HashMap<String, NpmLockDependency>,
+}
+
+/// Everything a lockfile records about a single resolved (package, version)
+/// pair beyond the version number itself. Both fields are sets because a
+/// single lockfile can list the same resolved version twice with different
+/// integrity algorithms (e.g. a `sha1` and a `sha512` digest for the same
+/// tarball).
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PackageVersionInfo {
+  #[serde(default)]
+  integrity: HashSet<String>,
+  #[serde(default)]
+  resolution: HashSet<String>,
+}
+
+type PackageVersions = HashMap<String, HashMap<String, PackageVersionInfo>>;
 
 #[derive(Debug, Default, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -34,15 +88,163 @@ pub fn get_changed_packages(
   env.to_js_value(&diff)
 }
 
+/// How significant a changed package's version bump is, in descending order
+/// of risk. `Changed` is the fallback for non-semver version strings (git
+/// refs, `file:` specifiers) that can't be classified any more precisely, and
+/// for packages whose highest version is the same on both sides (e.g. a
+/// version re-resolved to a different integrity or resolution URL).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+enum VersionChangeKind {
+  Added,
+  Removed,
+  Major,
+  Minor,
+  Patch,
+  Prerelease,
+  Changed,
+}
+
+#[napi]
+pub fn get_changed_packages_with_kind(
+  package_versions: JsObject,
+  prev_package_versions: JsObject,
+  env: Env,
+) -> napi::Result<JsUnknown> {
+  let classified = classify_changed_packages(
+    &env.from_js_value(prev_package_versions)?,
+    &env.from_js_value(package_versions)?,
+  );
+
+  env.to_js_value(&classified)
+}
+
+fn classify_changed_packages(
+  a: &PackageVersions,
+  b: &PackageVersions,
+) -> HashMap<String, VersionChangeKind> {
+  diff_package_versions(a, b)
+    .into_iter()
+    .map(|package| {
+      let kind = classify_version_change(a.get(&package), b.get(&package));
+      (package, kind)
+    })
+    .collect()
+}
+
+fn classify_version_change(
+  a_versions: Option<&HashMap<String, PackageVersionInfo>>,
+  b_versions: Option<&HashMap<String, PackageVersionInfo>>,
+) -> VersionChangeKind {
+  let (a_versions, b_versions) = match (a_versions, b_versions) {
+    (None, Some(_)) => return VersionChangeKind::Added,
+    (Some(_), None) => return VersionChangeKind::Removed,
+    (None, None) => return VersionChangeKind::Changed,
+    (Some(a), Some(b)) => (a, b),
+  };
+
+  let (Some(a_max), Some(b_max)) = (max_semver(a_versions.keys()), max_semver(b_versions.keys())) else {
+    return VersionChangeKind::Changed;
+  };
+
+  let (a_major, a_minor, a_patch, a_prerelease) = a_max;
+  let (b_major, b_minor, b_patch, b_prerelease) = b_max;
+
+  if a_major != b_major {
+    VersionChangeKind::Major
+  } else if a_minor != b_minor {
+    VersionChangeKind::Minor
+  } else if a_patch != b_patch {
+    VersionChangeKind::Patch
+  } else if a_prerelease != b_prerelease {
+    VersionChangeKind::Prerelease
+  } else {
+    VersionChangeKind::Changed
+  }
+}
+
+/// Parses a (loose) semver string into `(major, minor, patch, prerelease)`,
+/// returning `None` for non-semver version strings such as git refs or
+/// `file:` specifiers.
+fn parse_semver(version: &str) -> Option<(u64, u64, u64, Option<String>)> {
+  let (core, prerelease) = match version.split_once('-') {
+    Some((core, prerelease)) => (core, Some(prerelease.to_owned())),
+    None => (version, None),
+  };
+
+  let mut parts = core.splitn(3, '.');
+  let major = parts.next()?.parse().ok()?;
+  let minor = parts.next()?.parse().ok()?;
+  let patch = parts.next()?.parse().ok()?;
+
+  Some((major, minor, patch, prerelease))
+}
+
+/// The highest semver-parseable version in a set, by `(major, minor, patch)`
+/// precedence. Non-semver versions are ignored rather than causing a panic.
+fn max_semver<'a>(
+  versions: impl Iterator<Item = &'a String>,
+) -> Option<(u64, u64, u64, Option<String>)> {
+  versions
+    .filter_map(|version| parse_semver(version))
+    .max_by_key(|(major, minor, patch, _)| (*major, *minor, *patch))
+}
+
 #[napi]
-pub fn get_packages(yarn_lock_contents: String, env: Env) -> napi::Result<JsUnknown> {
-  match extract_yarn_metadata(&yarn_lock_contents) {
+pub fn get_packages(lockfile_contents: String, env: Env) -> napi::Result<JsUnknown> {
+  // package-lock.json is JSON; every yarn.lock format (classic or Berry) is not,
+  // so a leading `{` is enough to tell the two ecosystems apart.
+  let metadata = if lockfile_contents.trim_start().starts_with('{') {
+    extract_npm_metadata(&lockfile_contents)
+  } else {
+    extract_yarn_metadata(&lockfile_contents)
+  };
+
+  match metadata {
     Ok(metadata) => env.to_js_value(&metadata),
     Err(err) => Err(napi::Error::from_reason(format!("{:#}", err))),
   }
 }
 
+/// A directed edge from one resolved `name@version` node to another that it
+/// depends on.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DependencyEdge {
+  pub from: String,
+  pub to: String,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DependencyGraph {
+  pub nodes: Vec<String>,
+  pub edges: Vec<DependencyEdge>,
+}
+
+fn node_id(name: &str, version: &str) -> String {
+  format!("{name}@{version}")
+}
+
+#[napi]
+pub fn get_dependency_graph(lockfile_contents: String, env: Env) -> napi::Result<JsUnknown> {
+  let graph = if lockfile_contents.trim_start().starts_with('{') {
+    extract_npm_dependency_graph(&lockfile_contents)
+  } else {
+    extract_yarn_dependency_graph(&lockfile_contents)
+  };
+
+  match graph {
+    Ok(graph) => env.to_js_value(&graph),
+    Err(err) => Err(napi::Error::from_reason(format!("{:#}", err))),
+  }
+}
+
 fn extract_yarn_metadata(yarn_lock_contents: &str) -> anyhow::Result<PackageVersions> {
+  if is_yarn_v1_lockfile(yarn_lock_contents) {
+    return extract_yarn_v1_metadata(yarn_lock_contents);
+  }
+
   let yarn_lock: HashMap<String, YarnLockEntry> = serde_yaml::from_str(yarn_lock_contents)
     .with_context(|| "Failed to parse yarn.lock".to_string())?;
 
@@ -60,11 +262,180 @@ fn extract_yarn_metadata(yarn_lock_contents: &str) -> anyhow::Result<PackageVers
 
       let package = captures.get(1).unwrap().as_str();
 
-      if let Some(versions) = package_versions.get_mut(package) {
-        versions.insert(value.version.to_owned());
-      } else {
-        let versions = HashSet::from_iter(vec![value.version.to_owned()]);
-        package_versions.insert(package.to_owned(), versions);
+      insert_package_version(
+        &mut package_versions,
+        package.to_owned(),
+        value.version.to_owned(),
+        value.checksum.to_owned(),
+        value.resolution.to_owned(),
+      );
+    }
+  }
+
+  Ok(package_versions)
+}
+
+/// Records a (package, version) resolution, merging integrity/resolution
+/// into the existing entry's sets if one is already present.
+fn insert_package_version(
+  package_versions: &mut PackageVersions,
+  package: String,
+  version: String,
+  integrity: Option<String>,
+  resolution: Option<String>,
+) {
+  let info = package_versions
+    .entry(package)
+    .or_default()
+    .entry(version)
+    .or_default();
+
+  if let Some(integrity) = integrity {
+    info.integrity.insert(integrity);
+  }
+
+  if let Some(resolution) = resolution {
+    info.resolution.insert(resolution);
+  }
+}
+
+fn extract_yarn_dependency_graph(yarn_lock_contents: &str) -> anyhow::Result<DependencyGraph> {
+  if is_yarn_v1_lockfile(yarn_lock_contents) {
+    return extract_yarn_v1_dependency_graph(yarn_lock_contents);
+  }
+
+  let yarn_lock: HashMap<String, YarnLockEntry> = serde_yaml::from_str(yarn_lock_contents)
+    .with_context(|| "Failed to parse yarn.lock".to_string())?;
+
+  let descriptor_index = build_yarn_descriptor_index(&yarn_lock);
+  let yarn_lock_entry_re = Regex::new(r"(.+?)@npm:+")?;
+
+  let mut graph = DependencyGraph::default();
+
+  for (key, entry) in &yarn_lock {
+    if key == "__metadata" || entry.version == "0.0.0-use.local" {
+      continue;
+    }
+
+    let Some(captures) = yarn_lock_entry_re.captures(key) else {
+      continue;
+    };
+
+    let name = captures.get(1).unwrap().as_str();
+    let from = node_id(name, &entry.version);
+    graph.nodes.push(from.clone());
+
+    for (dep_name, dep_range) in &entry.dependencies {
+      let descriptor = format!("{dep_name}@npm:{dep_range}");
+
+      if let Some(dep_version) = descriptor_index.get(&descriptor) {
+        graph.edges.push(DependencyEdge {
+          from: from.clone(),
+          to: node_id(dep_name, dep_version),
+        });
+      }
+    }
+  }
+
+  Ok(graph)
+}
+
+/// Maps every descriptor a Berry yarn.lock resolves (e.g.
+/// `"lodash@npm:^4.0.0"`) to the version it was resolved to, so a
+/// dependency's `name`/range pair can be turned into a concrete node id.
+fn build_yarn_descriptor_index(yarn_lock: &HashMap<String, YarnLockEntry>) -> HashMap<String, String> {
+  let mut index = HashMap::new();
+
+  for (key, entry) in yarn_lock {
+    if key == "__metadata" {
+      continue;
+    }
+
+    for descriptor in key.split(", ") {
+      index.insert(
+        descriptor.trim().trim_matches('"').to_owned(),
+        entry.version.to_owned(),
+      );
+    }
+  }
+
+  index
+}
+
+/// Classic Yarn v1 lockfiles are a bespoke indentation-based format rather
+/// than YAML, and are identified by the `# yarn lockfile v1` header comment
+/// that the v1 CLI writes at the top of the file.
+fn is_yarn_v1_lockfile(yarn_lock_contents: &str) -> bool {
+  yarn_lock_contents
+    .lines()
+    .take(5)
+    .any(|line| line.trim() == "# yarn lockfile v1")
+}
+
+/// Parses a classic Yarn v1 lockfile by scanning comma-separated descriptor
+/// headers (e.g. `"lodash@^4.0.0", lodash@~4.1.0:`) followed by an indented
+/// `version "x.y.z"` line, rather than deserializing it as YAML.
+fn extract_yarn_v1_metadata(yarn_lock_contents: &str) -> anyhow::Result<PackageVersions> {
+  let mut package_versions: PackageVersions = HashMap::new();
+  let mut current_packages: Vec<String> = Vec::new();
+  let mut current_version: Option<String> = None;
+
+  for line in yarn_lock_contents.lines() {
+    if line.trim().is_empty() || line.starts_with('#') {
+      continue;
+    }
+
+    if !line.starts_with(' ') && !line.starts_with('\t') {
+      current_packages = line
+        .trim_end_matches(':')
+        .split(',')
+        .filter_map(|descriptor| {
+          let descriptor = descriptor.trim().trim_matches('"');
+          descriptor
+            .rfind('@')
+            .map(|range_start| descriptor[..range_start].to_string())
+        })
+        .collect();
+      current_version = None;
+      continue;
+    }
+
+    let line = line.trim();
+
+    if let Some(version) = line.strip_prefix("version ") {
+      let version = version.trim().trim_matches('"').to_string();
+      current_version = Some(version.clone());
+
+      for package in &current_packages {
+        insert_package_version(&mut package_versions, package.to_owned(), version.clone(), None, None);
+      }
+    } else if let Some(resolved) = line.strip_prefix("resolved ") {
+      let resolution = resolved.trim().trim_matches('"').to_string();
+
+      if let Some(version) = &current_version {
+        for package in &current_packages {
+          insert_package_version(
+            &mut package_versions,
+            package.to_owned(),
+            version.to_owned(),
+            None,
+            Some(resolution.clone()),
+          );
+        }
+      }
+    } else if let Some(integrity) = line.strip_prefix("integrity ") {
+      let integrity = integrity.trim().to_string();
+
+      if let Some(version) = &current_version {
+        for package in &current_packages {
+          insert_package_version(
+            &mut package_versions,
+            package.to_owned(),
+            version.to_owned(),
+            Some(integrity.clone()),
+            None,
+          );
+        }
       }
     }
   }
@@ -72,16 +443,348 @@ fn extract_yarn_metadata(yarn_lock_contents: &str) -> anyhow::Result<PackageVers
   Ok(package_versions)
 }
 
+/// Same indentation scan as [`extract_yarn_v1_metadata`], but also follows
+/// each entry's `dependencies:` sub-block (one indent level deeper than the
+/// entry's own `version`/`resolved`/`integrity` lines) to build edges.
+fn extract_yarn_v1_dependency_graph(yarn_lock_contents: &str) -> anyhow::Result<DependencyGraph> {
+  let descriptor_index = build_yarn_v1_descriptor_index(yarn_lock_contents);
+
+  let mut graph = DependencyGraph::default();
+  let mut current_packages: Vec<String> = Vec::new();
+  let mut current_version: Option<String> = None;
+  let mut in_dependencies_block = false;
+
+  for line in yarn_lock_contents.lines() {
+    if line.trim().is_empty() || line.starts_with('#') {
+      continue;
+    }
+
+    if !line.starts_with(' ') && !line.starts_with('\t') {
+      let mut packages: Vec<String> = line
+        .trim_end_matches(':')
+        .split(',')
+        .filter_map(|descriptor| {
+          let descriptor = descriptor.trim().trim_matches('"');
+          descriptor
+            .rfind('@')
+            .map(|range_start| descriptor[..range_start].to_string())
+        })
+        .collect();
+      // A header can list the same package multiple times at different
+      // ranges (e.g. `lodash@^4.0.0, lodash@~4.1.0:`); they all resolve to
+      // this one entry, so dedupe before pushing nodes/edges for it.
+      packages.sort();
+      packages.dedup();
+      current_packages = packages;
+      current_version = None;
+      in_dependencies_block = false;
+      continue;
+    }
+
+    let indent = line.len() - line.trim_start().len();
+    let line = line.trim();
+
+    if indent <= 2 {
+      in_dependencies_block = line == "dependencies:";
+
+      if let Some(version) = line.strip_prefix("version ") {
+        let version = version.trim().trim_matches('"').to_string();
+        current_version = Some(version.clone());
+
+        for package in &current_packages {
+          graph.nodes.push(node_id(package, &version));
+        }
+      }
+
+      continue;
+    }
+
+    if !in_dependencies_block {
+      continue;
+    }
+
+    let Some((dep_name, dep_range)) = parse_yarn_v1_dependency_line(line) else {
+      continue;
+    };
+    let Some(version) = &current_version else {
+      continue;
+    };
+    let Some(dep_version) = descriptor_index.get(&(dep_name.clone(), dep_range)) else {
+      continue;
+    };
+
+    for package in &current_packages {
+      graph.edges.push(DependencyEdge {
+        from: node_id(package, version),
+        to: node_id(&dep_name, dep_version),
+      });
+    }
+  }
+
+  Ok(graph)
+}
+
+/// A dependency sub-line looks like `"@babel/highlight" "^7.10.4"` (or
+/// unquoted for names without special characters).
+fn parse_yarn_v1_dependency_line(line: &str) -> Option<(String, String)> {
+  let mut parts = line.splitn(2, ' ');
+  let name = parts.next()?.trim_matches('"').to_string();
+  let range = parts.next()?.trim().trim_matches('"').to_string();
+  Some((name, range))
+}
+
+/// Maps every `(name, range)` descriptor a classic yarn.lock resolves to the
+/// version it was resolved to.
+fn build_yarn_v1_descriptor_index(yarn_lock_contents: &str) -> HashMap<(String, String), String> {
+  let mut index = HashMap::new();
+  let mut current_descriptors: Vec<(String, String)> = Vec::new();
+
+  for line in yarn_lock_contents.lines() {
+    if line.trim().is_empty() || line.starts_with('#') {
+      continue;
+    }
+
+    if !line.starts_with(' ') && !line.starts_with('\t') {
+      current_descriptors = line
+        .trim_end_matches(':')
+        .split(',')
+        .filter_map(|descriptor| {
+          let descriptor = descriptor.trim().trim_matches('"');
+          descriptor
+            .rfind('@')
+            .map(|range_start| (descriptor[..range_start].to_string(), descriptor[range_start + 1..].to_string()))
+        })
+        .collect();
+      continue;
+    }
+
+    let line = line.trim();
+    if let Some(version) = line.strip_prefix("version ") {
+      let version = version.trim().trim_matches('"').to_string();
+
+      for descriptor in &current_descriptors {
+        index.insert(descriptor.clone(), version.clone());
+      }
+    }
+  }
+
+  index
+}
+
+/// Parses a `package-lock.json`, supporting `lockfileVersion` 1, 2 and 3.
+fn extract_npm_metadata(package_lock_contents: &str) -> anyhow::Result<PackageVersions> {
+  let lockfile: NpmLockfile = serde_json::from_str(package_lock_contents)
+    .with_context(|| "Failed to parse package-lock.json".to_string())?;
+
+  let mut package_versions: PackageVersions = HashMap::new();
+
+  match lockfile.lockfile_version {
+    2 | 3 => {
+      for (path, entry) in &lockfile.packages {
+        // The root project itself is keyed by the empty string; skip it.
+        if path.is_empty() {
+          continue;
+        }
+
+        let (Some(name), Some(version)) = (npm_package_name_from_path(path), &entry.version)
+        else {
+          continue;
+        };
+
+        insert_package_version(
+          &mut package_versions,
+          name,
+          version.to_owned(),
+          entry.integrity.to_owned(),
+          entry.resolved.to_owned(),
+        );
+      }
+    }
+    1 => collect_npm_v1_dependencies(&lockfile.dependencies, &mut package_versions),
+    other => anyhow::bail!("Unsupported package-lock.json lockfileVersion: {other}"),
+  }
+
+  Ok(package_versions)
+}
+
+/// Derives a package name from a `lockfileVersion` 2/3 `packages` key, e.g.
+/// `node_modules/@scope/bar/node_modules/baz` -> `baz`, preserving the
+/// `@scope/` prefix for the final segment.
+fn npm_package_name_from_path(path: &str) -> Option<String> {
+  let index = path.rfind("node_modules/")?;
+  let name = &path[index + "node_modules/".len()..];
+
+  if name.is_empty() {
+    None
+  } else {
+    Some(name.to_owned())
+  }
+}
+
+fn collect_npm_v1_dependencies(
+  dependencies: &HashMap<String, NpmLockDependency>,
+  package_versions: &mut PackageVersions,
+) {
+  for (name, entry) in dependencies {
+    if let Some(version) = &entry.version {
+      insert_package_version(
+        package_versions,
+        name.to_owned(),
+        version.to_owned(),
+        entry.integrity.to_owned(),
+        entry.resolved.to_owned(),
+      );
+    }
+
+    collect_npm_v1_dependencies(&entry.dependencies, package_versions);
+  }
+}
+
+/// Parses a `package-lock.json`'s `dependencies` edges, supporting
+/// `lockfileVersion` 1, 2 and 3.
+fn extract_npm_dependency_graph(package_lock_contents: &str) -> anyhow::Result<DependencyGraph> {
+  let lockfile: NpmLockfile = serde_json::from_str(package_lock_contents)
+    .with_context(|| "Failed to parse package-lock.json".to_string())?;
+
+  let mut graph = DependencyGraph::default();
+
+  match lockfile.lockfile_version {
+    2 | 3 => {
+      for (path, entry) in &lockfile.packages {
+        if path.is_empty() {
+          continue;
+        }
+
+        let (Some(name), Some(version)) = (npm_package_name_from_path(path), &entry.version) else {
+          continue;
+        };
+
+        let from = node_id(&name, version);
+        graph.nodes.push(from.clone());
+
+        for dep_name in entry.dependencies.keys() {
+          if let Some(dep_version) = resolve_npm_dependency_version(&lockfile.packages, path, dep_name) {
+            graph.edges.push(DependencyEdge {
+              from: from.clone(),
+              to: node_id(dep_name, dep_version),
+            });
+          }
+        }
+      }
+    }
+    1 => collect_npm_v1_dependency_graph(&lockfile.dependencies, &mut Vec::new(), &mut graph),
+    other => anyhow::bail!("Unsupported package-lock.json lockfileVersion: {other}"),
+  }
+
+  Ok(graph)
+}
+
+/// Resolves a `lockfileVersion` 2/3 dependency by walking up the nesting
+/// chain of `node_modules` directories from the depending package's own
+/// path, falling back to the top-level `node_modules/<name>`, mirroring how
+/// npm itself resolves a require from nested `node_modules`.
+fn resolve_npm_dependency_version<'a>(
+  packages: &'a HashMap<String, NpmLockPackage>,
+  containing_path: &str,
+  dependency_name: &str,
+) -> Option<&'a String> {
+  let mut search_base = containing_path.to_owned();
+
+  loop {
+    let candidate = format!("{search_base}/node_modules/{dependency_name}");
+    if let Some(version) = packages.get(&candidate).and_then(|pkg| pkg.version.as_ref()) {
+      return Some(version);
+    }
+
+    match search_base.rfind("/node_modules/") {
+      Some(index) => search_base.truncate(index),
+      None => break,
+    }
+  }
+
+  let top_level = format!("node_modules/{dependency_name}");
+  packages.get(&top_level).and_then(|pkg| pkg.version.as_ref())
+}
+
+fn collect_npm_v1_dependency_graph<'a>(
+  dependencies: &'a HashMap<String, NpmLockDependency>,
+  scope_chain: &mut Vec<&'a HashMap<String, NpmLockDependency>>,
+  graph: &mut DependencyGraph,
+) {
+  scope_chain.push(dependencies);
+
+  for (name, entry) in dependencies {
+    if let Some(version) = &entry.version {
+      let from = node_id(name, version);
+      graph.nodes.push(from.clone());
+
+      // The entry's own nested `dependencies` are the nearest scope for its
+      // requires.
+      scope_chain.push(&entry.dependencies);
+
+      for dep_name in entry.requires.keys() {
+        if let Some(dep_version) = resolve_npm_v1_dependency_version(scope_chain, dep_name) {
+          graph.edges.push(DependencyEdge {
+            from: from.clone(),
+            to: node_id(dep_name, &dep_version),
+          });
+        }
+      }
+
+      scope_chain.pop();
+    }
+
+    collect_npm_v1_dependency_graph(&entry.dependencies, scope_chain, graph);
+  }
+
+  scope_chain.pop();
+}
+
+/// npm v1 lockfiles nest a dependency's own dependency tree underneath it,
+/// so the nearest enclosing scope that declares a name wins, falling back to
+/// outer scopes (closest to how node_modules hoisting resolves a require).
+fn resolve_npm_v1_dependency_version(
+  scope_chain: &[&HashMap<String, NpmLockDependency>],
+  dependency_name: &str,
+) -> Option<String> {
+  scope_chain
+    .iter()
+    .rev()
+    .find_map(|scope| scope.get(dependency_name))
+    .and_then(|entry| entry.version.to_owned())
+}
+
+/// A package is considered changed if its set of resolved versions differs,
+/// or if a version it shares with the other side was re-resolved to a
+/// different artifact (no integrity hash in common) or resolution URL.
 fn diff_package_versions(a: &PackageVersions, b: &PackageVersions) -> HashSet<String> {
   let mut diff = HashSet::new();
 
-  for (package, versions) in a {
-    if let Some(b_versions) = b.get(package) {
-      if versions != b_versions {
+  for (package, a_versions) in a {
+    let Some(b_versions) = b.get(package) else {
+      diff.insert(package.to_owned());
+      continue;
+    };
+
+    let a_version_set: HashSet<&String> = a_versions.keys().collect();
+    let b_version_set: HashSet<&String> = b_versions.keys().collect();
+
+    if a_version_set != b_version_set {
+      diff.insert(package.to_owned());
+      continue;
+    }
+
+    for (version, a_info) in a_versions {
+      let b_info = &b_versions[version];
+
+      let integrity_changed = !a_info.integrity.is_empty()
+        && !b_info.integrity.is_empty()
+        && a_info.integrity.is_disjoint(&b_info.integrity);
+
+      if integrity_changed || a_info.resolution != b_info.resolution {
         diff.insert(package.to_owned());
+        break;
       }
-    } else {
-      diff.insert(package.to_owned());
     }
   }
 
@@ -94,16 +797,154 @@ fn diff_package_versions(a: &PackageVersions, b: &PackageVersions) -> HashSet<St
   diff
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct SbomHash {
+  alg: String,
+  content: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SbomComponent {
+  #[serde(rename = "type")]
+  component_type: String,
+  #[serde(rename = "bom-ref")]
+  bom_ref: String,
+  name: String,
+  version: String,
+  purl: String,
+  hashes: Vec<SbomHash>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SbomDependency {
+  #[serde(rename = "ref")]
+  reference: String,
+  depends_on: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CycloneDxBom {
+  bom_format: String,
+  spec_version: String,
+  version: u32,
+  components: Vec<SbomComponent>,
+  dependencies: Vec<SbomDependency>,
+}
+
+#[napi]
+pub fn get_sbom(lockfile_contents: String, env: Env) -> napi::Result<JsUnknown> {
+  match generate_cyclonedx_sbom(&lockfile_contents) {
+    Ok(bom) => env.to_js_value(&bom),
+    Err(err) => Err(napi::Error::from_reason(format!("{:#}", err))),
+  }
+}
+
+/// Builds a CycloneDX 1.5 JSON BOM from a lockfile: every resolved
+/// (package, version) becomes a component with a `purl` and any hashes
+/// derived from its integrity field, and the already-parsed dependency
+/// graph becomes `dependencies[].dependsOn` relationships.
+fn generate_cyclonedx_sbom(lockfile_contents: &str) -> anyhow::Result<CycloneDxBom> {
+  let is_npm = lockfile_contents.trim_start().starts_with('{');
+
+  let package_versions = if is_npm {
+    extract_npm_metadata(lockfile_contents)?
+  } else {
+    extract_yarn_metadata(lockfile_contents)?
+  };
+
+  let dependency_graph = if is_npm {
+    extract_npm_dependency_graph(lockfile_contents)?
+  } else {
+    extract_yarn_dependency_graph(lockfile_contents)?
+  };
+
+  let mut components = Vec::new();
+
+  for (name, versions) in &package_versions {
+    for (version, info) in versions {
+      let hashes = info
+        .integrity
+        .iter()
+        .filter_map(|integrity| sri_to_cyclonedx_hash(integrity))
+        .collect();
+
+      components.push(SbomComponent {
+        component_type: "library".to_owned(),
+        bom_ref: node_id(name, version),
+        name: name.to_owned(),
+        version: version.to_owned(),
+        purl: npm_purl(name, version),
+        hashes,
+      });
+    }
+  }
+
+  let mut depends_on_by_ref: HashMap<String, Vec<String>> = HashMap::new();
+  for edge in &dependency_graph.edges {
+    depends_on_by_ref
+      .entry(edge.from.to_owned())
+      .or_default()
+      .push(edge.to.to_owned());
+  }
+
+  let dependencies = dependency_graph
+    .nodes
+    .iter()
+    .map(|node| SbomDependency {
+      reference: node.to_owned(),
+      depends_on: depends_on_by_ref.get(node).cloned().unwrap_or_default(),
+    })
+    .collect();
+
+  Ok(CycloneDxBom {
+    bom_format: "CycloneDX".to_owned(),
+    spec_version: "1.5".to_owned(),
+    version: 1,
+    components,
+    dependencies,
+  })
+}
+
+fn npm_purl(name: &str, version: &str) -> String {
+  format!("pkg:npm/{name}@{version}")
+}
+
+/// Converts a Subresource Integrity string (e.g. `sha512-<base64>`) into a
+/// CycloneDX hash, skipping algorithms CycloneDX doesn't define a value for.
+fn sri_to_cyclonedx_hash(integrity: &str) -> Option<SbomHash> {
+  let (alg, content) = integrity.split_once('-')?;
+
+  let alg = match alg.to_lowercase().as_str() {
+    "md5" => "MD5",
+    "sha1" => "SHA-1",
+    "sha256" => "SHA-256",
+    "sha384" => "SHA-384",
+    "sha512" => "SHA-512",
+    _ => return None,
+  };
+
+  Some(SbomHash {
+    alg: alg.to_owned(),
+    content: content.to_owned(),
+  })
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
 
+  // Most tests only care which versions were extracted, not the integrity/
+  // resolution metadata attached to them, so this projects `PackageVersions`
+  // down to a plain `package -> versions` map before comparing.
   macro_rules! assert_eq_package_versions {
     ($m: expr, $match: expr) => {{
       let mut map = HashMap::new();
       if let Ok(m) = $m {
-        for pair in m {
-          map.insert(pair.0, pair.1);
+        for (package, versions) in m {
+          map.insert(package, versions.into_keys().collect::<HashSet<_>>());
         }
       }
       assert_eq!(map, $match);
@@ -123,6 +964,26 @@ mod tests {
     };
   );
 
+  // Builds a `PackageVersions` with no integrity/resolution metadata, for
+  // tests that only exercise the version-set side of `diff_package_versions`.
+  macro_rules! version_map(
+    { $($key:expr => $value:expr),* } => {
+      {
+        #[allow(unused_mut)]
+        let mut m: PackageVersions = HashMap::new();
+        $(
+          let mut versions = HashMap::new();
+          for version in $value {
+            let version: &str = version;
+            versions.insert(version.to_owned(), PackageVersionInfo::default());
+          }
+          m.insert($key.into(), versions);
+        )*
+        m
+      }
+    };
+  );
+
   macro_rules! assert_set_values {
     ($m: expr, $match: expr) => {{
       let mut set = HashSet::new();
@@ -246,15 +1107,133 @@ mod tests {
     )
   }
 
+  #[test]
+  fn get_package_versions_v1() {
+    let yarn_lock = r#"# THIS IS AN AUTOGENERATED FILE. DO NOT EDIT THIS FILE DIRECTLY.
+# yarn lockfile v1
+
+
+"@babel/code-frame@^7.0.0":
+  version "7.12.11"
+  resolved "https://registry.yarnpkg.com/@babel/code-frame/-/code-frame-7.12.11.tgz#f4ad435aa263db935b8dd9f25c7f5cc8a18ec02"
+  integrity sha512-Zt1yodBx1UcyiePMSkWnU4hPqhwq7hGi2nFL1LeA3EUl+k6r+UwhWIcMgSy2&+
+  dependencies:
+    "@babel/highlight" "^7.10.4"
+
+lodash@^4.0.0, lodash@~4.1.0:
+  version "4.17.21"
+  resolved "https://registry.yarnpkg.com/lodash/-/lodash-4.17.21.tgz#679591c564c3bffaae8454cf0b3df370c3d6911c"
+  integrity sha512-v2kDEe57lecTulaDIuNTPy3Ry4+ZjNfGOwxhLD7h8UdiMGQ+b29RL91UfQJdFXlnPTzQi+v+4ElUPiVJw==
+"#;
+
+    assert_eq_package_versions!(
+      extract_yarn_metadata(yarn_lock),
+      map! {
+        "@babel/code-frame" => vec!["7.12.11"],
+        "lodash" => vec!["4.17.21"]
+      }
+    )
+  }
+
+  #[test]
+  fn v1_keys_with_multiple_declarations_share_a_version() {
+    let yarn_lock = r#"# yarn lockfile v1
+
+"@apollo/react-components@^3.1.2", "@apollo/react-components@^3.1.3":
+  version "3.1.5"
+  resolved "https://registry.yarnpkg.com/@apollo/react-components/-/react-components-3.1.5.tgz"
+  integrity sha512-abc123==
+"#;
+
+    assert_eq_package_versions!(
+      extract_yarn_metadata(yarn_lock),
+      map! {
+        "@apollo/react-components" => vec!["3.1.5"]
+      }
+    )
+  }
+
+  #[test]
+  fn get_package_versions_npm_v3() {
+    let package_lock = r#"{
+      "name": "my-project",
+      "lockfileVersion": 3,
+      "packages": {
+        "": {
+          "name": "my-project",
+          "version": "1.0.0"
+        },
+        "node_modules/lodash": {
+          "version": "4.17.21"
+        },
+        "node_modules/@scope/bar": {
+          "version": "2.0.0"
+        },
+        "node_modules/@scope/bar/node_modules/baz": {
+          "version": "1.2.3"
+        },
+        "node_modules/local-link": {
+          "link": true
+        }
+      }
+    }"#;
+
+    assert_eq_package_versions!(
+      extract_npm_metadata(package_lock),
+      map! {
+        "lodash" => vec!["4.17.21"],
+        "@scope/bar" => vec!["2.0.0"],
+        "baz" => vec!["1.2.3"]
+      }
+    )
+  }
+
+  #[test]
+  fn get_package_versions_npm_v1() {
+    let package_lock = r#"{
+      "name": "my-project",
+      "lockfileVersion": 1,
+      "dependencies": {
+        "lodash": {
+          "version": "4.17.21"
+        },
+        "some-package": {
+          "version": "1.0.0",
+          "dependencies": {
+            "nested-package": {
+              "version": "0.5.0"
+            }
+          }
+        }
+      }
+    }"#;
+
+    assert_eq_package_versions!(
+      extract_npm_metadata(package_lock),
+      map! {
+        "lodash" => vec!["4.17.21"],
+        "some-package" => vec!["1.0.0"],
+        "nested-package" => vec!["0.5.0"]
+      }
+    )
+  }
+
+  #[test]
+  fn errors_on_unknown_npm_lockfile_version() {
+    let package_lock = r#"{ "lockfileVersion": 99, "packages": {} }"#;
+
+    assert!(extract_npm_metadata(package_lock).is_err());
+  }
+
   #[test]
   fn diff_with_bump() {
     assert_set_values!(
       diff_package_versions(
-        &map! {
+        &version_map! {
           "some-package" => vec!["1.0.0"],
           "unchanged-package" => vec!["2.0.0"]
         },
-        &map! {
+        &version_map! {
           "some-package" => vec!["1.2.3"],
           "unchanged-package" => vec!["2.0.0"]
         }
@@ -267,11 +1246,11 @@ mod tests {
   fn diff_with_addition() {
     assert_set_values!(
       diff_package_versions(
-        &map! {
+        &version_map! {
           "some-package" => vec!["1.0.0"],
           "unchanged-package" => vec!["2.0.0"]
         },
-        &map! {
+        &version_map! {
           "some-package" => vec!["1.0.0", "1.2.3"],
           "unchanged-package" => vec!["2.0.0"],
           "new-package" => vec!["3.0.0"]
@@ -285,12 +1264,12 @@ mod tests {
   fn diff_with_removal() {
     assert_set_values!(
       diff_package_versions(
-        &map! {
+        &version_map! {
           "some-package" => vec!["1.0.0", "1.2.3"],
           "unchanged-package" => vec!["2.0.0"],
           "removed-package" => vec!["3.0.0"]
         },
-        &map! {
+        &version_map! {
           "some-package" => vec!["1.0.0"],
           "unchanged-package" => vec!["2.0.0"]
         },
@@ -298,4 +1277,408 @@ mod tests {
       vec!["some-package", "removed-package"]
     )
   }
+
+  fn with_resolution(integrity: &[&str], resolution: &[&str]) -> PackageVersionInfo {
+    PackageVersionInfo {
+      integrity: integrity.iter().map(|s| s.to_string()).collect(),
+      resolution: resolution.iter().map(|s| s.to_string()).collect(),
+    }
+  }
+
+  #[test]
+  fn diff_flags_same_version_reresolved_to_different_integrity() {
+    let mut a: PackageVersions = HashMap::new();
+    a.entry("some-package".to_string()).or_default().insert(
+      "1.0.0".to_string(),
+      with_resolution(&["sha512-aaa"], &["https://registry/some-package-1.0.0.tgz"]),
+    );
+
+    let mut b: PackageVersions = HashMap::new();
+    b.entry("some-package".to_string()).or_default().insert(
+      "1.0.0".to_string(),
+      with_resolution(&["sha512-bbb"], &["https://registry/some-package-1.0.0.tgz"]),
+    );
+
+    assert_set_values!(diff_package_versions(&a, &b), vec!["some-package"])
+  }
+
+  #[test]
+  fn diff_ignores_overlapping_integrity_from_multiple_algorithms() {
+    let mut a: PackageVersions = HashMap::new();
+    a.entry("some-package".to_string()).or_default().insert(
+      "1.0.0".to_string(),
+      with_resolution(
+        &["sha1-aaa", "sha512-bbb"],
+        &["https://registry/some-package-1.0.0.tgz"],
+      ),
+    );
+
+    let mut b: PackageVersions = HashMap::new();
+    b.entry("some-package".to_string()).or_default().insert(
+      "1.0.0".to_string(),
+      with_resolution(&["sha512-bbb"], &["https://registry/some-package-1.0.0.tgz"]),
+    );
+
+    let empty: Vec<&str> = Vec::new();
+    assert_set_values!(diff_package_versions(&a, &b), empty)
+  }
+
+  #[test]
+  fn diff_flags_resolution_url_change_at_same_version() {
+    let mut a: PackageVersions = HashMap::new();
+    a.entry("some-package".to_string())
+      .or_default()
+      .insert("1.0.0".to_string(), with_resolution(&[], &["https://mirror-a/some-package-1.0.0.tgz"]));
+
+    let mut b: PackageVersions = HashMap::new();
+    b.entry("some-package".to_string())
+      .or_default()
+      .insert("1.0.0".to_string(), with_resolution(&[], &["https://mirror-b/some-package-1.0.0.tgz"]));
+
+    assert_set_values!(diff_package_versions(&a, &b), vec!["some-package"])
+  }
+
+  #[test]
+  fn classifies_major_minor_patch_and_prerelease_bumps() {
+    assert_eq!(
+      classify_version_change(
+        Some(&version_map! { "_" => vec!["1.0.0"] }["_"]),
+        Some(&version_map! { "_" => vec!["2.0.0"] }["_"])
+      ),
+      VersionChangeKind::Major
+    );
+
+    assert_eq!(
+      classify_version_change(
+        Some(&version_map! { "_" => vec!["1.0.0"] }["_"]),
+        Some(&version_map! { "_" => vec!["1.1.0"] }["_"])
+      ),
+      VersionChangeKind::Minor
+    );
+
+    assert_eq!(
+      classify_version_change(
+        Some(&version_map! { "_" => vec!["1.0.0"] }["_"]),
+        Some(&version_map! { "_" => vec!["1.0.1"] }["_"])
+      ),
+      VersionChangeKind::Patch
+    );
+
+    assert_eq!(
+      classify_version_change(
+        Some(&version_map! { "_" => vec!["1.0.0-alpha.1"] }["_"]),
+        Some(&version_map! { "_" => vec!["1.0.0-alpha.2"] }["_"])
+      ),
+      VersionChangeKind::Prerelease
+    );
+  }
+
+  #[test]
+  fn classifies_added_and_removed_packages() {
+    assert_eq!(
+      classify_version_change(None, Some(&version_map! { "_" => vec!["1.0.0"] }["_"])),
+      VersionChangeKind::Added
+    );
+
+    assert_eq!(
+      classify_version_change(Some(&version_map! { "_" => vec!["1.0.0"] }["_"]), None),
+      VersionChangeKind::Removed
+    );
+  }
+
+  #[test]
+  fn non_semver_versions_fall_back_to_changed() {
+    assert_eq!(
+      classify_version_change(
+        Some(&version_map! { "_" => vec!["some-branch"] }["_"]),
+        Some(&version_map! { "_" => vec!["some-other-branch"] }["_"])
+      ),
+      VersionChangeKind::Changed
+    );
+  }
+
+  #[test]
+  fn get_changed_packages_with_kind_reports_version_classification() {
+    let prev = version_map! {
+      "some-package" => vec!["1.0.0"],
+      "removed-package" => vec!["1.0.0"]
+    };
+    let next = version_map! {
+      "some-package" => vec!["2.0.0"],
+      "new-package" => vec!["1.0.0"]
+    };
+
+    let classified = classify_changed_packages(&prev, &next);
+
+    assert_eq!(
+      classified.get("some-package"),
+      Some(&VersionChangeKind::Major)
+    );
+    assert_eq!(
+      classified.get("removed-package"),
+      Some(&VersionChangeKind::Removed)
+    );
+    assert_eq!(classified.get("new-package"), Some(&VersionChangeKind::Added));
+  }
+
+  #[test]
+  fn dependency_graph_from_berry_yarn_lock() {
+    let yarn_lock = r#"
+    __metadata:
+        version: 6
+        cacheKey: 8
+
+    "some-package@npm:^1.0.0":
+        version: 1.0.0
+        resolution: "some-package@npm:1.0.0"
+        checksum: abc
+        languageName: node
+        linkType: hard
+        dependencies:
+          lodash: ^4.0.0
+
+    "lodash@npm:^4.0.0":
+        version: 4.17.21
+        resolution: "lodash@npm:4.17.21"
+        checksum: def
+        languageName: node
+        linkType: hard
+    "#;
+
+    let graph = extract_yarn_dependency_graph(yarn_lock).unwrap();
+
+    assert!(graph.nodes.contains(&"some-package@1.0.0".to_string()));
+    assert!(graph.nodes.contains(&"lodash@4.17.21".to_string()));
+    assert!(graph.edges.iter().any(|edge| edge.from == "some-package@1.0.0"
+      && edge.to == "lodash@4.17.21"));
+  }
+
+  #[test]
+  fn dependency_graph_from_v1_yarn_lock() {
+    let yarn_lock = r#"# yarn lockfile v1
+
+some-package@^1.0.0:
+  version "1.0.0"
+  resolved "https://registry.yarnpkg.com/some-package/-/some-package-1.0.0.tgz"
+  integrity sha512-aaa==
+  dependencies:
+    lodash "^4.0.0"
+
+lodash@^4.0.0:
+  version "4.17.21"
+  resolved "https://registry.yarnpkg.com/lodash/-/lodash-4.17.21.tgz"
+  integrity sha512-bbb==
+"#;
+
+    let graph = extract_yarn_dependency_graph(yarn_lock).unwrap();
+
+    assert!(graph.nodes.contains(&"some-package@1.0.0".to_string()));
+    assert!(graph.nodes.contains(&"lodash@4.17.21".to_string()));
+    assert!(graph.edges.iter().any(|edge| edge.from == "some-package@1.0.0"
+      && edge.to == "lodash@4.17.21"));
+  }
+
+  #[test]
+  fn dependency_graph_from_v1_yarn_lock_dedupes_multi_descriptor_header() {
+    let yarn_lock = r#"# yarn lockfile v1
+
+some-package@^1.0.0:
+  version "1.0.0"
+  resolved "https://registry.yarnpkg.com/some-package/-/some-package-1.0.0.tgz"
+  integrity sha512-aaa==
+  dependencies:
+    lodash "^4.0.0"
+
+lodash@^4.0.0, lodash@~4.1.0:
+  version "4.17.21"
+  resolved "https://registry.yarnpkg.com/lodash/-/lodash-4.17.21.tgz"
+  integrity sha512-bbb==
+"#;
+
+    let graph = extract_yarn_dependency_graph(yarn_lock).unwrap();
+
+    assert_eq!(
+      graph
+        .nodes
+        .iter()
+        .filter(|node| *node == "lodash@4.17.21")
+        .count(),
+      1
+    );
+    assert_eq!(
+      graph
+        .edges
+        .iter()
+        .filter(|edge| edge.from == "some-package@1.0.0" && edge.to == "lodash@4.17.21")
+        .count(),
+      1
+    );
+  }
+
+  #[test]
+  fn dependency_graph_from_npm_v3_lockfile() {
+    let package_lock = r#"{
+      "name": "my-project",
+      "lockfileVersion": 3,
+      "packages": {
+        "": { "name": "my-project", "version": "1.0.0" },
+        "node_modules/some-package": {
+          "version": "1.0.0",
+          "dependencies": { "lodash": "^4.0.0" }
+        },
+        "node_modules/lodash": {
+          "version": "4.17.21"
+        }
+      }
+    }"#;
+
+    let graph = extract_npm_dependency_graph(package_lock).unwrap();
+
+    assert!(graph.nodes.contains(&"some-package@1.0.0".to_string()));
+    assert!(graph.nodes.contains(&"lodash@4.17.21".to_string()));
+    assert!(graph.edges.iter().any(|edge| edge.from == "some-package@1.0.0"
+      && edge.to == "lodash@4.17.21"));
+  }
+
+  #[test]
+  fn dependency_graph_from_npm_v3_lockfile_resolves_nested_override() {
+    let package_lock = r#"{
+      "name": "my-project",
+      "lockfileVersion": 3,
+      "packages": {
+        "": { "name": "my-project", "version": "1.0.0" },
+        "node_modules/lodash": {
+          "version": "4.17.21"
+        },
+        "node_modules/some-package": {
+          "version": "1.0.0",
+          "dependencies": { "lodash": "^3.0.0" }
+        },
+        "node_modules/some-package/node_modules/lodash": {
+          "version": "3.10.1"
+        }
+      }
+    }"#;
+
+    let graph = extract_npm_dependency_graph(package_lock).unwrap();
+
+    assert!(graph.edges.iter().any(|edge| edge.from == "some-package@1.0.0"
+      && edge.to == "lodash@3.10.1"));
+  }
+
+  #[test]
+  fn dependency_graph_from_npm_v1_lockfile() {
+    let package_lock = r#"{
+      "name": "my-project",
+      "lockfileVersion": 1,
+      "dependencies": {
+        "some-package": {
+          "version": "1.0.0",
+          "requires": { "lodash": "^4.0.0" }
+        },
+        "lodash": {
+          "version": "4.17.21"
+        }
+      }
+    }"#;
+
+    let graph = extract_npm_dependency_graph(package_lock).unwrap();
+
+    assert!(graph.edges.iter().any(|edge| edge.from == "some-package@1.0.0"
+      && edge.to == "lodash@4.17.21"));
+  }
+
+  #[test]
+  fn sbom_component_carries_purl_and_hash_and_dependency_from_npm_lockfile() {
+    let package_lock = r#"{
+      "name": "my-project",
+      "lockfileVersion": 3,
+      "packages": {
+        "": { "name": "my-project", "version": "1.0.0" },
+        "node_modules/@scope/some-package": {
+          "version": "1.0.0",
+          "integrity": "sha512-abc123==",
+          "dependencies": { "lodash": "^4.0.0" }
+        },
+        "node_modules/lodash": {
+          "version": "4.17.21",
+          "integrity": "sha1-def456=="
+        }
+      }
+    }"#;
+
+    let bom = generate_cyclonedx_sbom(package_lock).unwrap();
+
+    assert_eq!(bom.bom_format, "CycloneDX");
+
+    let scoped_component = bom
+      .components
+      .iter()
+      .find(|component| component.name == "@scope/some-package")
+      .unwrap();
+    assert_eq!(scoped_component.version, "1.0.0");
+    assert_eq!(scoped_component.purl, "pkg:npm/@scope/some-package@1.0.0");
+    assert_eq!(scoped_component.hashes[0].alg, "SHA-512");
+    assert_eq!(scoped_component.hashes[0].content, "abc123==");
+
+    let lodash_dependency = bom
+      .dependencies
+      .iter()
+      .find(|dependency| dependency.reference == "@scope/some-package@1.0.0")
+      .unwrap();
+    assert_eq!(lodash_dependency.depends_on, vec!["lodash@4.17.21"]);
+  }
+
+  #[test]
+  fn sbom_skips_unknown_integrity_algorithms() {
+    let package_lock = r#"{
+      "name": "my-project",
+      "lockfileVersion": 3,
+      "packages": {
+        "": { "name": "my-project", "version": "1.0.0" },
+        "node_modules/some-package": {
+          "version": "1.0.0",
+          "integrity": "crc32-zzz=="
+        }
+      }
+    }"#;
+
+    let bom = generate_cyclonedx_sbom(package_lock).unwrap();
+    let component = bom
+      .components
+      .iter()
+      .find(|component| component.name == "some-package")
+      .unwrap();
+
+    assert!(component.hashes.is_empty());
+  }
+
+  #[test]
+  fn sbom_from_v1_yarn_lock_does_not_duplicate_dependency_refs() {
+    let yarn_lock = r#"# yarn lockfile v1
+
+some-package@^1.0.0:
+  version "1.0.0"
+  resolved "https://registry.yarnpkg.com/some-package/-/some-package-1.0.0.tgz"
+  integrity sha512-aaa==
+  dependencies:
+    lodash "^4.0.0"
+
+lodash@^4.0.0, lodash@~4.1.0:
+  version "4.17.21"
+  resolved "https://registry.yarnpkg.com/lodash/-/lodash-4.17.21.tgz"
+  integrity sha512-bbb==
+"#;
+
+    let bom = generate_cyclonedx_sbom(yarn_lock).unwrap();
+
+    assert_eq!(
+      bom
+        .dependencies
+        .iter()
+        .filter(|dependency| dependency.reference == "lodash@4.17.21")
+        .count(),
+      1
+    );
+  }
 }